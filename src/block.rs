@@ -0,0 +1,61 @@
+use serde::Deserialize;
+
+/// A `getBlock` RPC result with `encoding: "json"`.
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmedBlock {
+    pub blockhash: String,
+    pub previous_blockhash: String,
+    pub parent_slot: u64,
+    pub block_time: Option<i64>,
+    pub block_height: Option<u64>,
+    pub transactions: Vec<EncodedTransactionWithMeta>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct EncodedTransactionWithMeta {
+    pub transaction: serde_json::Value,
+    pub meta: Option<TransactionMeta>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMeta {
+    pub fee: u64,
+    pub compute_units_consumed: Option<u64>,
+    pub log_messages: Option<Vec<String>>,
+    pub err: Option<serde_json::Value>,
+}
+
+/// Aggregate per-block metrics derived from a [`ConfirmedBlock`], used for
+/// reporting instead of just a raw transaction count.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BlockMetrics {
+    pub tx_count: usize,
+    pub failed_tx_count: usize,
+    pub total_fee_lamports: u64,
+    pub total_compute_units: u64,
+}
+
+impl ConfirmedBlock {
+    pub fn metrics(&self) -> BlockMetrics {
+        let mut metrics = BlockMetrics {
+            tx_count: self.transactions.len(),
+            ..Default::default()
+        };
+
+        for tx in &self.transactions {
+            let Some(meta) = &tx.meta else { continue };
+            if meta.err.is_some() {
+                metrics.failed_tx_count += 1;
+            }
+            metrics.total_fee_lamports += meta.fee;
+            metrics.total_compute_units += meta.compute_units_consumed.unwrap_or(0);
+        }
+
+        metrics
+    }
+}
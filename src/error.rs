@@ -0,0 +1,60 @@
+/// What the fetch loop should do in response to a JSON-RPC error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchAction {
+    /// Transient condition; wait and re-request the same slot.
+    Retry,
+    /// The slot will never produce a block; advance past it.
+    Skip,
+    /// Unrecoverable for this run; surface the error and exit.
+    Fatal,
+}
+
+/// Known Solana JSON-RPC server error codes, classified by how the fetch
+/// loop should react to them. See
+/// <https://docs.solana.com/api/http#json-rpc-error-codes> for the full list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolanaRpcError {
+    BlockCleanedUp,
+    BlockNotAvailable,
+    NodeUnhealthy,
+    TransactionHistoryNotAvailable,
+    SlotSkipped,
+    LongTermStorageSlotSkipped,
+    UnsupportedTransactionVersion,
+    BlockStatusNotAvailableYet,
+    MinContextSlotNotReached,
+    Unknown(i64),
+}
+
+impl SolanaRpcError {
+    pub fn from_code(code: i64) -> Self {
+        match code {
+            -32001 => Self::BlockCleanedUp,
+            -32004 => Self::BlockNotAvailable,
+            -32005 => Self::NodeUnhealthy,
+            -32007 => Self::SlotSkipped,
+            -32009 => Self::LongTermStorageSlotSkipped,
+            -32011 => Self::TransactionHistoryNotAvailable,
+            -32014 => Self::BlockStatusNotAvailableYet,
+            -32015 => Self::UnsupportedTransactionVersion,
+            -32016 => Self::MinContextSlotNotReached,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// The action the fetch loop should take for this error. Unrecognized
+    /// codes are treated as fatal rather than busy-spinning forever.
+    pub fn action(self) -> FetchAction {
+        match self {
+            Self::BlockNotAvailable
+            | Self::BlockStatusNotAvailableYet
+            | Self::NodeUnhealthy
+            | Self::MinContextSlotNotReached => FetchAction::Retry,
+            Self::SlotSkipped | Self::LongTermStorageSlotSkipped => FetchAction::Skip,
+            Self::BlockCleanedUp
+            | Self::TransactionHistoryNotAvailable
+            | Self::UnsupportedTransactionVersion
+            | Self::Unknown(_) => FetchAction::Fatal,
+        }
+    }
+}
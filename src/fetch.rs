@@ -0,0 +1,297 @@
+use crate::block::ConfirmedBlock;
+use crate::error::{FetchAction, SolanaRpcError};
+use crate::http::HttpConfig;
+use crate::rpc::{JsonRpcRequest, JsonRpcResponse};
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use reqwest::Client;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_FETCH_CONCURRENCY: usize = 8;
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Outcome of fetching a single slot.
+pub enum SlotResult {
+    Block(ConfirmedBlock, Duration),
+    Skipped,
+    Empty,
+    CleanedUp { first_available_block: u64 },
+    Fatal(String),
+    TransportError(String),
+}
+
+type SlotFuture = Pin<Box<dyn Future<Output = (u64, SlotResult, u32)> + Send>>;
+
+/// Fetches a single slot, retrying in place (via internal sleep) on a
+/// retryable RPC error so that one slow slot never blocks the rest of the
+/// in-flight window. Returns how many retries it took alongside the result,
+/// so callers can feed that into [`crate::stats::Stats`].
+async fn fetch_slot(
+    client: Client,
+    rpc_url: String,
+    cfg: serde_json::Value,
+    slot: u64,
+    http_config: HttpConfig,
+) -> (u64, SlotResult, u32) {
+    let mut retries = 0;
+
+    loop {
+        let req = JsonRpcRequest::new(2, "getBlock", serde_json::json!([slot, cfg.clone()]));
+        let start = Instant::now();
+
+        let parsed: Result<JsonRpcResponse<ConfirmedBlock>, reqwest::Error> =
+            crate::http::post_with_retry(&client, &rpc_url, &req, &http_config).await;
+
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => return (slot, SlotResult::TransportError(err.to_string()), retries),
+        };
+        let latency = start.elapsed();
+
+        if let Some(error) = parsed.error {
+            let classified = SolanaRpcError::from_code(error.code);
+
+            if classified == SolanaRpcError::BlockCleanedUp {
+                if let Some(first_available_block) = error.first_available_block() {
+                    return (
+                        slot,
+                        SlotResult::CleanedUp {
+                            first_available_block,
+                        },
+                        retries,
+                    );
+                }
+            }
+
+            match classified.action() {
+                FetchAction::Retry => {
+                    retries += 1;
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    continue;
+                }
+                FetchAction::Skip => return (slot, SlotResult::Skipped, retries),
+                FetchAction::Fatal => {
+                    return (slot, SlotResult::Fatal(error.to_string()), retries)
+                }
+            }
+        }
+
+        return match parsed.result {
+            Some(block) => (slot, SlotResult::Block(block, latency), retries),
+            None => (slot, SlotResult::Empty, retries),
+        };
+    }
+}
+
+/// Where upcoming slots to fetch come from: either an auto-incrementing
+/// contiguous tail, or an explicit list of already-known-produced slots
+/// (e.g. from a `getBlocks` range query), which skips long stretches of
+/// empty leader slots without a `getBlock` round-trip for each one.
+///
+/// `Explicit` tracks dispatch order (`dispatch`, drained as requests go out,
+/// which may run ahead of emission under concurrency) separately from emit
+/// order (`emit_order`, drained as results are yielded back to the caller
+/// in slot order) — the produced-slot list is sparse, so "the next slot to
+/// emit" is whatever is next in the list, not `emitted + 1`.
+enum SlotSource {
+    Contiguous(u64),
+    Explicit {
+        dispatch: VecDeque<u64>,
+        emit_order: VecDeque<u64>,
+    },
+}
+
+impl SlotSource {
+    fn next_to_dispatch(&mut self) -> Option<u64> {
+        match self {
+            SlotSource::Contiguous(next_slot) => {
+                let slot = *next_slot;
+                *next_slot += 1;
+                Some(slot)
+            }
+            SlotSource::Explicit { dispatch, .. } => dispatch.pop_front(),
+        }
+    }
+
+    /// The slot that should be emitted after `emitted_slot`, which has just
+    /// been yielded to the caller.
+    fn advance_emit(&mut self, emitted_slot: u64) -> u64 {
+        match self {
+            SlotSource::Contiguous(_) => emitted_slot + 1,
+            SlotSource::Explicit { emit_order, .. } => {
+                debug_assert_eq!(emit_order.pop_front(), Some(emitted_slot));
+                emit_order.front().copied().unwrap_or(u64::MAX)
+            }
+        }
+    }
+
+    fn advance_past(&mut self, slot: u64) {
+        match self {
+            SlotSource::Contiguous(next_slot) => *next_slot = (*next_slot).max(slot),
+            SlotSource::Explicit {
+                dispatch,
+                emit_order,
+            } => {
+                dispatch.retain(|&s| s >= slot);
+                emit_order.retain(|&s| s >= slot);
+            }
+        }
+    }
+}
+
+/// Fetches a window of slots with up to `concurrency` in-flight `getBlock`
+/// requests, yielding results back in slot order via a small reorder
+/// buffer. A retryable error re-enqueues only the affected slot (internally,
+/// via `fetch_slot`'s own retry loop) instead of stalling the rest of the
+/// window.
+pub struct PipelinedFetcher {
+    client: Client,
+    rpc_url: String,
+    get_block_cfg: serde_json::Value,
+    http_config: HttpConfig,
+    concurrency: usize,
+    source: SlotSource,
+    next_to_emit: u64,
+    in_flight: FuturesUnordered<SlotFuture>,
+    buffer: BTreeMap<u64, (SlotResult, u32)>,
+}
+
+impl PipelinedFetcher {
+    /// Tails the chain starting at `start_slot`, requesting every
+    /// subsequent slot in order.
+    pub fn new(
+        client: Client,
+        rpc_url: String,
+        get_block_cfg: serde_json::Value,
+        http_config: HttpConfig,
+        concurrency: usize,
+        start_slot: u64,
+    ) -> Self {
+        Self::with_source(
+            client,
+            rpc_url,
+            get_block_cfg,
+            http_config,
+            concurrency,
+            SlotSource::Contiguous(start_slot),
+            start_slot,
+        )
+    }
+
+    /// Fetches exactly the given slots, in order, skipping everything else.
+    /// Intended for a pre-filtered produced-slot list from a `getBlocks`
+    /// range query, so skipped stretches never cost a `getBlock` round-trip.
+    pub fn new_with_explicit_slots(
+        client: Client,
+        rpc_url: String,
+        get_block_cfg: serde_json::Value,
+        http_config: HttpConfig,
+        concurrency: usize,
+        slots: VecDeque<u64>,
+    ) -> Self {
+        let next_to_emit = slots.front().copied().unwrap_or(u64::MAX);
+        let emit_order = slots.clone();
+        Self::with_source(
+            client,
+            rpc_url,
+            get_block_cfg,
+            http_config,
+            concurrency,
+            SlotSource::Explicit {
+                dispatch: slots,
+                emit_order,
+            },
+            next_to_emit,
+        )
+    }
+
+    fn with_source(
+        client: Client,
+        rpc_url: String,
+        get_block_cfg: serde_json::Value,
+        http_config: HttpConfig,
+        concurrency: usize,
+        source: SlotSource,
+        next_to_emit: u64,
+    ) -> Self {
+        PipelinedFetcher {
+            client,
+            rpc_url,
+            get_block_cfg,
+            http_config,
+            concurrency: concurrency.max(1),
+            source,
+            next_to_emit,
+            in_flight: FuturesUnordered::new(),
+            buffer: BTreeMap::new(),
+        }
+    }
+
+    fn spawn(&mut self, slot: u64) {
+        let future = fetch_slot(
+            self.client.clone(),
+            self.rpc_url.clone(),
+            self.get_block_cfg.clone(),
+            slot,
+            self.http_config,
+        );
+        self.in_flight.push(Box::pin(future));
+    }
+
+    fn fill_window(&mut self) {
+        while self.in_flight.len() < self.concurrency {
+            let Some(slot) = self.source.next_to_dispatch() else {
+                break;
+            };
+            self.spawn(slot);
+        }
+    }
+
+    /// Jumps the window forward (e.g. after a cleaned-up block), discarding
+    /// any now-stale buffered results below `slot`.
+    fn jump_to(&mut self, slot: u64) {
+        self.next_to_emit = slot;
+        self.source.advance_past(slot);
+        self.buffer.retain(|&s, _| s >= slot);
+    }
+
+    /// Returns the next `(slot, result, retries)` triple in slot order.
+    pub async fn next(&mut self) -> Option<(u64, SlotResult, u32)> {
+        loop {
+            self.fill_window();
+
+            if let Some((result, retries)) = self.buffer.remove(&self.next_to_emit) {
+                let slot = self.next_to_emit;
+                match &result {
+                    SlotResult::CleanedUp {
+                        first_available_block,
+                    } => self.jump_to(*first_available_block),
+                    _ => self.next_to_emit = self.source.advance_emit(slot),
+                }
+                return Some((slot, result, retries));
+            }
+
+            let (slot, result, retries) = self.in_flight.next().await?;
+
+            if slot < self.next_to_emit {
+                continue; // stale result from before a jump; drop it
+            }
+
+            if slot > self.next_to_emit {
+                self.buffer.insert(slot, (result, retries));
+                continue;
+            }
+
+            match &result {
+                SlotResult::CleanedUp {
+                    first_available_block,
+                } => self.jump_to(*first_available_block),
+                _ => self.next_to_emit = self.source.advance_emit(slot),
+            }
+            return Some((slot, result, retries));
+        }
+    }
+}
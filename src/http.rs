@@ -0,0 +1,86 @@
+use rand::Rng;
+use std::time::Duration;
+
+pub const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+pub const DEFAULT_BASE_DELAY_MS: u64 = 200;
+const MAX_BACKOFF_MS: u64 = 30_000;
+
+/// Configuration for HTTP-level resilience: request timeout and the
+/// exponential backoff applied to transport-layer retries (connection
+/// resets, timeouts), as opposed to JSON-RPC application errors, which are
+/// handled separately via [`crate::error::FetchAction`].
+#[derive(Debug, Clone, Copy)]
+pub struct HttpConfig {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_delay: Duration::from_millis(DEFAULT_BASE_DELAY_MS),
+        }
+    }
+}
+
+impl HttpConfig {
+    pub fn from_env() -> Self {
+        HttpConfig {
+            timeout: Duration::from_millis(crate::env_var_or("HTTP_TIMEOUT_MS", DEFAULT_TIMEOUT_MS)),
+            max_retries: crate::env_var_or("HTTP_MAX_RETRIES", DEFAULT_MAX_RETRIES),
+            base_delay: Duration::from_millis(crate::env_var_or(
+                "HTTP_RETRY_BASE_DELAY_MS",
+                DEFAULT_BASE_DELAY_MS,
+            )),
+        }
+    }
+
+    pub fn build_client(&self) -> reqwest::Result<reqwest::Client> {
+        reqwest::Client::builder().timeout(self.timeout).build()
+    }
+
+    /// Exponential backoff with full jitter for the given (1-based) retry
+    /// attempt.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exp_ms = self
+            .base_delay
+            .as_millis()
+            .saturating_mul(1 << attempt.min(16)) as u64;
+        let capped_ms = exp_ms.min(MAX_BACKOFF_MS);
+        Duration::from_millis(rand::thread_rng().gen_range(0..=capped_ms))
+    }
+}
+
+/// Posts `body` to `url` and deserializes the response, retrying
+/// transport-layer failures (connection errors, timeouts) up to
+/// `config.max_retries` times with exponential backoff and jitter.
+pub async fn post_with_retry<B, T>(
+    client: &reqwest::Client,
+    url: &str,
+    body: &B,
+    config: &HttpConfig,
+) -> Result<T, reqwest::Error>
+where
+    B: serde::Serialize + ?Sized,
+    T: serde::de::DeserializeOwned,
+{
+    let mut attempt = 0;
+    loop {
+        // Only the transport-level `send` is retried here; a response that
+        // arrived but failed to deserialize is a schema/decode problem, not
+        // a transient one, so it's returned immediately instead of being
+        // retried `max_retries` times.
+        match client.post(url).json(body).send().await {
+            Ok(resp) => return resp.json().await,
+            Err(_) if attempt < config.max_retries => {
+                attempt += 1;
+                tokio::time::sleep(config.backoff_delay(attempt)).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
@@ -1,21 +1,114 @@
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::time::{Duration, Instant};
+mod block;
+mod error;
+mod fetch;
+mod http;
+mod range;
+mod rpc;
+mod stats;
+
+use fetch::{PipelinedFetcher, SlotResult, DEFAULT_FETCH_CONCURRENCY};
+use http::HttpConfig;
+use rpc::JsonRpcResponse;
+use stats::Stats;
+
+/// How often (in blocks fetched) to print a latency/throughput summary.
+const STATS_SUMMARY_INTERVAL: u64 = 100;
+
+/// How a fetcher's drain loop ended.
+enum DrainOutcome {
+    /// The fetcher ran out of slots to fetch (only happens for an explicit,
+    /// bounded slot list; a contiguous tail never stops on its own).
+    Exhausted,
+    /// The user hit Ctrl-C.
+    Interrupted,
+    /// The server returned no block data for a requested slot.
+    StoppedAtEmptyBlock,
+}
+
+/// Drains `fetcher` to completion (or interruption), printing per-block
+/// output and feeding `stats` as results come in.
+async fn drain_fetcher(
+    fetcher: &mut PipelinedFetcher,
+    stats: &mut Stats,
+) -> Result<DrainOutcome, Box<dyn std::error::Error>> {
+    loop {
+        let next = tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                println!("received Ctrl-C, shutting down");
+                return Ok(DrainOutcome::Interrupted);
+            }
+            next = fetcher.next() => next,
+        };
+
+        let Some((slot, result, retries)) = next else {
+            return Ok(DrainOutcome::Exhausted);
+        };
+        for _ in 0..retries {
+            stats.record_retry();
+        }
+
+        match result {
+            SlotResult::Block(block, latency) => {
+                let metrics = block.metrics();
+                stats.record_block(latency, metrics.tx_count);
+                println!(
+                    "slot: {} | tx_count: {} | failed: {} | fees: {} lamports | compute_units: {} | latency: {:?}",
+                    slot,
+                    metrics.tx_count,
+                    metrics.failed_tx_count,
+                    metrics.total_fee_lamports,
+                    metrics.total_compute_units,
+                    latency
+                );
+                if stats.blocks_observed() % STATS_SUMMARY_INTERVAL == 0 {
+                    stats.print_summary();
+                }
+            }
+            SlotResult::Skipped => {
+                stats.record_skip();
+                println!("slot: {} | skipped", slot);
+            }
+            SlotResult::Empty => {
+                println!("no block data found for slot: {}", slot);
+                return Ok(DrainOutcome::StoppedAtEmptyBlock);
+            }
+            SlotResult::CleanedUp {
+                first_available_block,
+            } => {
+                println!(
+                    "slot: {} | cleaned up, jumping to first available block: {}",
+                    slot, first_available_block
+                );
+            }
+            SlotResult::Fatal(message) | SlotResult::TransportError(message) => {
+                stats.print_summary();
+                return Err(message.into());
+            }
+        }
+    }
+}
 
-const SOLANA_BLOCK_NOT_AVAILABLE_ERROR: i64 = -32004;
-const SOLANA_BLOCK_SKIPPED_ERROR: i64 = -32007;
-const FETCH_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Reads an environment variable and parses it, falling back to `default`
+/// if it's unset or fails to parse.
+fn env_var_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let rpc_url =
         std::env::var("SOLANA_RPC_URL").expect("`SOLANA_RPC_URL` environment variable must be set");
+    let concurrency = env_var_or("FETCH_CONCURRENCY", DEFAULT_FETCH_CONCURRENCY);
+    let http_config = HttpConfig::from_env();
 
-    let client = Client::new();
+    let client = http_config.build_client()?;
 
-    let get_slot_req = JsonRpcRequest::new(1, "getSlot", serde_json::json!([]));
-    let get_slot_resp = client.post(&rpc_url).json(&get_slot_req).send().await?;
-    let get_slot_resp: JsonRpcResponse<u64> = get_slot_resp.json().await?;
+    let get_slot_req = rpc::JsonRpcRequest::new(1, "getSlot", serde_json::json!([]));
+    let get_slot_resp: JsonRpcResponse<u64> =
+        http::post_with_retry(&client, &rpc_url, &get_slot_req, &http_config).await?;
 
     if let Some(error) = get_slot_resp.error {
         return Err(error.into());
@@ -29,96 +122,62 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "transactionDetails": "full",
         "rewards": false
     });
-    let mut slot_to_fetch = latest_slot;
-
-    println!("fetching blocks starting from slot: {}", slot_to_fetch);
-
-    loop {
-        let block_req = JsonRpcRequest::new(
-            2,
-            "getBlock",
-            serde_json::json!([slot_to_fetch, get_block_cfg]),
-        );
 
-        let start = Instant::now();
-        let block_resp = client.post(&rpc_url).json(&block_req).send().await?;
-        let get_block_resp: JsonRpcResponse<serde_json::Value> = block_resp.json().await?;
-        let latency = start.elapsed();
-
-        if let Some(error) = get_block_resp.error {
-            if error.code == SOLANA_BLOCK_NOT_AVAILABLE_ERROR {
-                tokio::time::sleep(FETCH_RETRY_DELAY).await;
-            } else if error.code == SOLANA_BLOCK_SKIPPED_ERROR {
-                println!("slot: {} | skipped", slot_to_fetch);
-                slot_to_fetch += 1;
-            } else {
-                println!("error: {}", error);
-            }
-            continue;
-        }
-
-        let Some(block) = get_block_resp.result else {
-            println!("no block data found for slot: {}", slot_to_fetch);
-            break;
-        };
+    println!(
+        "latest slot: {} (concurrency: {})",
+        latest_slot, concurrency
+    );
 
-        let Some(txs) = block.get("transactions").and_then(|t| t.as_array()) else {
-            println!("no transactions found for slot: {}", slot_to_fetch);
-            break;
-        };
+    let mut stats = Stats::new();
+    let mut tail_start_slot = latest_slot;
 
+    let get_blocks_lookback: u64 = env_var_or("GET_BLOCKS_LOOKBACK_WINDOW", 0);
+    if get_blocks_lookback > 0 {
+        let window_start = latest_slot.saturating_sub(get_blocks_lookback);
         println!(
-            "slot: {} | tx_count: {} | latency: {:?}",
-            slot_to_fetch,
-            txs.len(),
-            latency
+            "querying getBlocks for slots {}..={} before tailing",
+            window_start, latest_slot
         );
 
-        slot_to_fetch += 1;
-    }
-
-    Ok(())
-}
+        let produced =
+            range::produced_slots_in_range(&client, &rpc_url, &http_config, window_start, latest_slot)
+                .await?;
+        let elapsed_slots = latest_slot.saturating_sub(window_start) + 1;
+        println!(
+            "getBlocks window: {} of {} slots produced ({:.1}%)",
+            produced.len(),
+            elapsed_slots,
+            produced.len() as f64 / elapsed_slots as f64 * 100.0
+        );
 
-#[derive(Debug, Serialize, Deserialize)]
-struct JsonRpcRequest {
-    jsonrpc: String,
-    id: u64,
-    method: String,
-    params: serde_json::Value,
-}
+        let mut backlog_fetcher = PipelinedFetcher::new_with_explicit_slots(
+            client.clone(),
+            rpc_url.clone(),
+            get_block_cfg.clone(),
+            http_config,
+            concurrency,
+            produced.into(),
+        );
 
-impl JsonRpcRequest {
-    fn new(id: u64, method: &str, params: serde_json::Value) -> Self {
-        JsonRpcRequest {
-            jsonrpc: String::from("2.0"),
-            id,
-            method: method.to_owned(),
-            params,
+        match drain_fetcher(&mut backlog_fetcher, &mut stats).await? {
+            DrainOutcome::Exhausted => tail_start_slot = latest_slot + 1,
+            DrainOutcome::Interrupted | DrainOutcome::StoppedAtEmptyBlock => {
+                stats.print_summary();
+                return Ok(());
+            }
         }
     }
-}
-
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-struct JsonRpcResponse<T> {
-    jsonrpc: String,
-    id: u64,
-    result: Option<T>,
-    error: Option<JsonRpcError>,
-}
 
-#[derive(Debug, Deserialize)]
-struct JsonRpcError {
-    code: i64,
-    message: String,
-}
-
-impl std::fmt::Display for JsonRpcError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "JSON-RPC error {}: {}", self.code, self.message)
-    }
+    let mut fetcher = PipelinedFetcher::new(
+        client,
+        rpc_url,
+        get_block_cfg,
+        http_config,
+        concurrency,
+        tail_start_slot,
+    );
+    drain_fetcher(&mut fetcher, &mut stats).await?;
+
+    stats.print_summary();
+    Ok(())
 }
-
-impl std::error::Error for JsonRpcError {}
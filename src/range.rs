@@ -0,0 +1,38 @@
+use crate::http::HttpConfig;
+use crate::rpc::{JsonRpcRequest, JsonRpcResponse};
+use reqwest::Client;
+
+/// Solana caps a single `getBlocks` query to a 500,000-slot range.
+const MAX_GET_BLOCKS_RANGE: u64 = 500_000;
+
+/// Returns the slots in `[start_slot, end_slot]` that actually produced a
+/// block, fetched via `getBlocks` in chunks no larger than the server's
+/// 500k-slot range cap. Used to skip long stretches of empty leader slots
+/// without a `getBlock` round-trip for each one.
+pub async fn produced_slots_in_range(
+    client: &Client,
+    rpc_url: &str,
+    http_config: &HttpConfig,
+    start_slot: u64,
+    end_slot: u64,
+) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let mut produced = Vec::new();
+    let mut chunk_start = start_slot;
+
+    while chunk_start <= end_slot {
+        let chunk_end = (chunk_start + MAX_GET_BLOCKS_RANGE - 1).min(end_slot);
+
+        let req = JsonRpcRequest::new(3, "getBlocks", serde_json::json!([chunk_start, chunk_end]));
+        let resp: JsonRpcResponse<Vec<u64>> =
+            crate::http::post_with_retry(client, rpc_url, &req, http_config).await?;
+
+        if let Some(error) = resp.error {
+            return Err(Box::new(error));
+        }
+
+        produced.extend(resp.result.unwrap_or_default());
+        chunk_start = chunk_end + 1;
+    }
+
+    Ok(produced)
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+    jsonrpc: String,
+    id: u64,
+    method: String,
+    params: serde_json::Value,
+}
+
+impl JsonRpcRequest {
+    pub fn new(id: u64, method: &str, params: serde_json::Value) -> Self {
+        JsonRpcRequest {
+            jsonrpc: String::from("2.0"),
+            id,
+            method: method.to_owned(),
+            params,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonRpcResponse<T> {
+    jsonrpc: String,
+    id: u64,
+    pub result: Option<T>,
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<serde_json::Value>,
+}
+
+impl JsonRpcError {
+    /// The first block still within the retention window, if this is a
+    /// `BlockCleanedUp` (-32001) error and the server reported one.
+    pub fn first_available_block(&self) -> Option<u64> {
+        if self.code != -32001 {
+            return None;
+        }
+        self.data.as_ref()?.get("firstAvailableBlock")?.as_u64()
+    }
+}
+
+impl std::fmt::Display for JsonRpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "JSON-RPC error {}: {}", self.code, self.message)?;
+        if let Some(data) = &self.data {
+            write!(f, " (data: {})", data)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for JsonRpcError {}
@@ -0,0 +1,136 @@
+use std::time::{Duration, Instant};
+
+const MIN_LATENCY_MS: f64 = 1.0;
+const MAX_LATENCY_MS: f64 = 30_000.0;
+const BUCKET_COUNT: usize = 128;
+
+/// Fixed-bucket, log-scaled latency histogram covering 1ms..30s. Memory
+/// stays constant regardless of how many samples are recorded, which
+/// matters for long-running benchmark sessions.
+struct LatencyHistogram {
+    buckets: [u64; BUCKET_COUNT],
+    sum_ms: f64,
+    count: u64,
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        LatencyHistogram {
+            buckets: [0; BUCKET_COUNT],
+            sum_ms: 0.0,
+            count: 0,
+        }
+    }
+
+    fn record(&mut self, latency: Duration) {
+        let ms = (latency.as_secs_f64() * 1000.0).clamp(MIN_LATENCY_MS, MAX_LATENCY_MS);
+        self.buckets[Self::bucket_for(ms)] += 1;
+        self.sum_ms += ms;
+        self.count += 1;
+    }
+
+    fn bucket_for(ms: f64) -> usize {
+        let frac = (ms.ln() - MIN_LATENCY_MS.ln()) / (MAX_LATENCY_MS.ln() - MIN_LATENCY_MS.ln());
+        ((frac * (BUCKET_COUNT - 1) as f64).ceil() as usize).min(BUCKET_COUNT - 1)
+    }
+
+    /// Upper latency bound represented by `bucket`, used as the percentile
+    /// estimate for samples that fall in it.
+    fn bucket_upper_bound_ms(bucket: usize) -> f64 {
+        let frac = bucket as f64 / (BUCKET_COUNT - 1) as f64;
+        (MIN_LATENCY_MS.ln() + frac * (MAX_LATENCY_MS.ln() - MIN_LATENCY_MS.ln())).exp()
+    }
+
+    fn mean_ms(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_ms / self.count as f64
+        }
+    }
+
+    /// Approximate percentile latency, accurate to the width of the bucket
+    /// it falls in.
+    fn percentile_ms(&self, p: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let target = (p * self.count as f64).ceil() as u64;
+        let mut cumulative = 0u64;
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return Self::bucket_upper_bound_ms(bucket);
+            }
+        }
+        MAX_LATENCY_MS
+    }
+}
+
+/// Aggregated statistics for an RPC benchmarking run: latency distribution,
+/// throughput, and retry/skip counts.
+pub struct Stats {
+    histogram: LatencyHistogram,
+    min_latency: Option<Duration>,
+    max_latency: Option<Duration>,
+    blocks_observed: u64,
+    transactions_observed: u64,
+    retries_observed: u64,
+    skips_observed: u64,
+    started_at: Instant,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats {
+            histogram: LatencyHistogram::new(),
+            min_latency: None,
+            max_latency: None,
+            blocks_observed: 0,
+            transactions_observed: 0,
+            retries_observed: 0,
+            skips_observed: 0,
+            started_at: Instant::now(),
+        }
+    }
+
+    pub fn record_block(&mut self, latency: Duration, tx_count: usize) {
+        self.histogram.record(latency);
+        self.min_latency = Some(self.min_latency.map_or(latency, |m| m.min(latency)));
+        self.max_latency = Some(self.max_latency.map_or(latency, |m| m.max(latency)));
+        self.blocks_observed += 1;
+        self.transactions_observed += tx_count as u64;
+    }
+
+    pub fn record_retry(&mut self) {
+        self.retries_observed += 1;
+    }
+
+    pub fn record_skip(&mut self) {
+        self.skips_observed += 1;
+    }
+
+    pub fn blocks_observed(&self) -> u64 {
+        self.blocks_observed
+    }
+
+    /// Prints a one-line latency/throughput summary to stdout.
+    pub fn print_summary(&self) {
+        let elapsed = self.started_at.elapsed().as_secs_f64().max(f64::EPSILON);
+        println!(
+            "stats | blocks: {} | txs: {} | retries: {} | skips: {} | slots/sec: {:.2} \
+             | latency(ms) min: {:.0} mean: {:.0} p50: {:.0} p90: {:.0} p99: {:.0} max: {:.0}",
+            self.blocks_observed,
+            self.transactions_observed,
+            self.retries_observed,
+            self.skips_observed,
+            (self.blocks_observed + self.skips_observed) as f64 / elapsed,
+            self.min_latency.map_or(0.0, |d| d.as_secs_f64() * 1000.0),
+            self.histogram.mean_ms(),
+            self.histogram.percentile_ms(0.50),
+            self.histogram.percentile_ms(0.90),
+            self.histogram.percentile_ms(0.99),
+            self.max_latency.map_or(0.0, |d| d.as_secs_f64() * 1000.0),
+        );
+    }
+}